@@ -0,0 +1,97 @@
+//! Turns a byte offset into the source text into a human-readable location
+//! (`line:column`) and a caret-underlined snippet, so tokenizer and parser
+//! errors can point at exactly where things went wrong.
+
+use super::lexer::Span;
+
+/// A 1-indexed line/column location within a source string.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Maps a byte offset into `source` to its 1-indexed line and column.
+///
+/// `column` counts *characters*, not bytes, so a multi-byte UTF-8 character
+/// before `offset` doesn't shift later columns past where the caret in
+/// `render` actually lands.
+pub fn locate(source: &str, offset: usize) -> Location {
+    let offset = offset.min(source.len());
+    let mut line = 1;
+    let mut line_start = 0;
+    for (i, b) in source.as_bytes()[..offset].iter().enumerate() {
+        if *b == b'\n' {
+            line += 1;
+            line_start = i + 1;
+        }
+    }
+    Location {
+        line,
+        column: source[line_start..offset].chars().count() + 1,
+    }
+}
+
+/// Renders a diagnostic for `span` in `source`: a message line giving the
+/// location, followed by the offending source line with a caret underneath
+/// the span's start, e.g.:
+///
+/// ```text
+/// error: expected colon at 3:11
+///     "key" "value"
+///           ^
+/// ```
+pub fn render(source: &str, span: Span, message: &str) -> String {
+    let loc = locate(source, span.start);
+    let line_text = source.lines().nth(loc.line - 1).unwrap_or("");
+    let caret = " ".repeat(loc.column.saturating_sub(1)) + "^";
+    format!("error: {message} at {loc}\n{line_text}\n{caret}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_first_line() {
+        let loc = locate("abc", 1);
+        assert_eq!(loc, Location { line: 1, column: 2 });
+    }
+
+    #[test]
+    fn locates_across_newlines() {
+        let source = "ab\ncd\nef";
+        assert_eq!(locate(source, 0), Location { line: 1, column: 1 });
+        assert_eq!(locate(source, 3), Location { line: 2, column: 1 });
+        assert_eq!(locate(source, 7), Location { line: 3, column: 2 });
+    }
+
+    #[test]
+    fn renders_caret_under_span_start() {
+        let source = "{\"a\": }";
+        let rendered = render(source, Span::new(6, 7), "expected value");
+        assert_eq!(rendered, "error: expected value at 1:7\n{\"a\": }\n      ^");
+    }
+
+    #[test]
+    fn locates_by_char_not_byte_for_multibyte_utf8() {
+        let source = "{\"é\": }";
+        let span_start = source.find('}').unwrap();
+        let loc = locate(source, span_start);
+        assert_eq!(loc, Location { line: 1, column: 7 });
+    }
+
+    #[test]
+    fn renders_caret_correctly_past_multibyte_utf8() {
+        let source = "{\"é\": }";
+        let span_start = source.find('}').unwrap();
+        let rendered = render(source, Span::new(span_start, span_start + 1), "expected value");
+        assert_eq!(rendered, "error: expected value at 1:7\n{\"é\": }\n      ^");
+    }
+}
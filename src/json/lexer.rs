@@ -1,8 +1,29 @@
-use std::iter::Peekable;
+use std::borrow::Cow;
 
-/// An enum representing all the possible Token types in a JSON object
+/// A byte-offset span `[start, end)` into the original input, attached to
+/// every token so later stages (errors, diagnostics) can point back at the
+/// exact source text that produced it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+/// An enum representing all the possible Token types in a JSON object.
+///
+/// `String` borrows directly from the input the `Tokenizer` was built from
+/// whenever the literal has no escapes, so tokenizing a document without
+/// them never allocates; a literal containing `\n`, `\uXXXX`, etc. is
+/// decoded into an owned `String` instead, since the decoded text doesn't
+/// appear verbatim in the source.
 #[derive(Debug, PartialEq, Clone)]
-pub enum Token {
+pub enum Token<'a> {
     CurlyOpen,
     CurlyClose,
     SquareOpen,
@@ -10,7 +31,7 @@ pub enum Token {
     Comma,
     Colon,
     WhiteSpace,
-    String(String),
+    String(Cow<'a, str>),
     Number(f64),
     Bool(bool),
     Null,
@@ -18,38 +39,69 @@ pub enum Token {
 
 #[derive(Debug)]
 pub enum TokenizerError {
-    InvalidString,
-    InvalidNumber,
-    InvalidLiteral,
+    InvalidString(Span),
+    InvalidNumber(Span),
+    InvalidLiteral(Span),
+}
+
+impl TokenizerError {
+    /// The span of input that triggered this error.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::InvalidString(span) => *span,
+            Self::InvalidNumber(span) => *span,
+            Self::InvalidLiteral(span) => *span,
+        }
+    }
 }
 
 impl std::fmt::Display for TokenizerError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            Self::InvalidString => write!(f, "Invalid string token"),
-            Self::InvalidNumber => write!(f, "Invalid number token"),
-            Self::InvalidLiteral => write!(f, "Invalid literal token"),
+            Self::InvalidString(_) => write!(f, "Invalid string token"),
+            Self::InvalidNumber(_) => write!(f, "Invalid number token"),
+            Self::InvalidLiteral(_) => write!(f, "Invalid literal token"),
         }
     }
 }
 
 impl std::error::Error for TokenizerError {}
 
-/// A Struct for handling tokenization of JSON objects
+/// A lexing error that can say where in the input it happened, so a
+/// `Parser` generic over its token source can render a located diagnostic
+/// without knowing the concrete error type a particular lexer produces.
+pub trait LocatedError: std::fmt::Display {
+    fn location(&self) -> Span;
+}
+
+impl LocatedError for TokenizerError {
+    fn location(&self) -> Span {
+        self.span()
+    }
+}
+
+/// A Struct for handling tokenization of JSON objects.
+///
+/// Holds a borrowed view of the input rather than an owned copy, so tokens
+/// sliced out of it (`Token::String`) can borrow straight from the source
+/// document with no per-token allocation.
 #[derive(Debug, PartialEq, Clone)]
-pub struct Tokenizer {
-    pub input: Vec<u8>,
+pub struct Tokenizer<'a> {
+    pub source: &'a str,
+    idx: usize,
 }
 
-impl Tokenizer {
-    pub fn new(input_string: &str) -> Self {
-        Self {
-            input: input_string.as_bytes().to_vec(),
-        }
+impl<'a> Tokenizer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self { source, idx: 0 }
     }
 
-    fn get_token(&self, idx: usize) -> Result<Token, TokenizerError> {
-        match self.input[idx] {
+    fn bytes(&self) -> &'a [u8] {
+        self.source.as_bytes()
+    }
+
+    fn get_token(&self, idx: usize) -> Result<Token<'a>, TokenizerError> {
+        match self.bytes()[idx] {
             b'{' => Ok(Token::CurlyOpen),
             b'}' => Ok(Token::CurlyClose),
             b',' => Ok(Token::Comma),
@@ -65,85 +117,280 @@ impl Tokenizer {
         }
     }
 
-    fn validate_string_token(&self, idx: usize) -> Result<Token, TokenizerError> {
-        let end = self.input[idx + 1..]
-            .windows(2)
-            .position(|window| window == [b'"'] || (window[0] != b'\\' && window[1] == b'"'))
-            .map(|pos| pos + 1);
-        match end {
-            Some(end) => {
-                let output = String::from_utf8(self.input[idx + 1..idx + 1 + end].to_vec());
-                match output {
-                    Ok(output) => Ok(Token::String(output)),
-                    Err(_) => Err(TokenizerError::InvalidString),
+    /// Scans a string token starting at the opening `"` at `idx` with a
+    /// forward pass that treats `\\` as escaping whatever follows it, so an
+    /// escaped quote right after the opening delimiter is handled
+    /// correctly. Returns the token's total byte length (both quotes
+    /// included) on success, or how far the scan got before running off
+    /// the end of the input unterminated.
+    fn scan_string(&self, idx: usize) -> Result<usize, usize> {
+        let bytes = self.bytes();
+        let mut pos = idx + 1;
+        loop {
+            match bytes.get(pos) {
+                None => return Err(pos - idx),
+                Some(b'"') => return Ok(pos + 1 - idx),
+                Some(b'\\') => pos += 2,
+                Some(_) => pos += 1,
+            }
+        }
+    }
+
+    /// Reads the four hex digits at `pos` as a UTF-16 code unit.
+    fn read_hex4(&self, pos: usize) -> Option<u32> {
+        let digits = std::str::from_utf8(self.bytes().get(pos..pos + 4)?).ok()?;
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    fn validate_string_token(&self, idx: usize) -> Result<Token<'a>, TokenizerError> {
+        let len = self
+            .scan_string(idx)
+            .map_err(|scanned| TokenizerError::InvalidString(Span::new(idx, idx + scanned)))?;
+        let content_start = idx + 1;
+        let content_end = idx + len - 1;
+
+        let bytes = self.bytes();
+        let mut pos = content_start;
+        let mut buf: Option<String> = None;
+        let mut flushed = content_start;
+
+        while pos < content_end {
+            if bytes[pos] != b'\\' {
+                pos += 1;
+                continue;
+            }
+
+            let out = buf.get_or_insert_with(String::new);
+            out.push_str(&self.source[flushed..pos]);
+            pos += 1;
+            match bytes.get(pos) {
+                Some(b'"') => {
+                    out.push('"');
+                    pos += 1;
+                }
+                Some(b'\\') => {
+                    out.push('\\');
+                    pos += 1;
+                }
+                Some(b'/') => {
+                    out.push('/');
+                    pos += 1;
+                }
+                Some(b'b') => {
+                    out.push('\u{0008}');
+                    pos += 1;
                 }
+                Some(b'f') => {
+                    out.push('\u{000C}');
+                    pos += 1;
+                }
+                Some(b'n') => {
+                    out.push('\n');
+                    pos += 1;
+                }
+                Some(b'r') => {
+                    out.push('\r');
+                    pos += 1;
+                }
+                Some(b't') => {
+                    out.push('\t');
+                    pos += 1;
+                }
+                Some(b'u') => {
+                    pos += 1;
+                    let unit = self
+                        .read_hex4(pos)
+                        .ok_or_else(|| TokenizerError::InvalidString(Span::new(idx, pos)))?;
+                    pos += 4;
+                    let scalar = if (0xD800..=0xDBFF).contains(&unit) {
+                        let low = (bytes.get(pos) == Some(&b'\\') && bytes.get(pos + 1) == Some(&b'u'))
+                            .then(|| self.read_hex4(pos + 2))
+                            .flatten()
+                            .filter(|low| (0xDC00..=0xDFFF).contains(low))
+                            .ok_or_else(|| TokenizerError::InvalidString(Span::new(idx, pos)))?;
+                        pos += 6;
+                        0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00)
+                    } else if (0xDC00..=0xDFFF).contains(&unit) {
+                        return Err(TokenizerError::InvalidString(Span::new(idx, pos)));
+                    } else {
+                        unit
+                    };
+                    let ch = char::from_u32(scalar)
+                        .ok_or_else(|| TokenizerError::InvalidString(Span::new(idx, pos)))?;
+                    out.push(ch);
+                }
+                _ => return Err(TokenizerError::InvalidString(Span::new(idx, pos))),
             }
-            None => Err(TokenizerError::InvalidString),
+            flushed = pos;
         }
+
+        Ok(Token::String(match buf {
+            Some(mut out) => {
+                out.push_str(&self.source[flushed..content_end]);
+                Cow::Owned(out)
+            }
+            None => Cow::Borrowed(&self.source[content_start..content_end]),
+        }))
     }
 
-    fn validate_number_token(&self, idx: usize) -> Result<Token, TokenizerError> {
-        let end = self.input[idx..]
-            .iter()
-            .position(|&val| !(val.is_ascii_digit() || val == b'.' || val == b'-'))
-            .unwrap_or(self.input.len() - idx);
-        let num = String::from_utf8(self.input[idx..idx + end].to_vec())
-            .unwrap()
-            .parse::<f64>();
-        match num {
-            Ok(num) => Ok(Token::Number(num)),
-            Err(_) => Err(TokenizerError::InvalidNumber),
+    /// Scans the RFC 8259 number grammar starting at `idx`: an optional
+    /// leading `-`, an integer part (`0` or `[1-9][0-9]*`, no leading
+    /// zeros), an optional `.` fraction with at least one digit, and an
+    /// optional `[eE][+-]?` exponent with at least one digit.
+    ///
+    /// Returns the token's byte length on success, or how many bytes were
+    /// scanned before the grammar was violated on failure.
+    fn scan_number(&self, idx: usize) -> Result<usize, usize> {
+        let bytes = self.bytes();
+        let mut pos = idx;
+
+        if bytes.get(pos) == Some(&b'-') {
+            pos += 1;
+        }
+
+        match bytes.get(pos) {
+            Some(b'0') => pos += 1,
+            Some(b'1'..=b'9') => {
+                pos += 1;
+                while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+                    pos += 1;
+                }
+            }
+            _ => return Err(pos - idx),
+        }
+
+        if bytes.get(pos) == Some(&b'.') {
+            let dot = pos;
+            pos += 1;
+            let digits_start = pos;
+            while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+                pos += 1;
+            }
+            if pos == digits_start {
+                return Err(dot - idx);
+            }
+        }
+
+        if matches!(bytes.get(pos), Some(b'e') | Some(b'E')) {
+            let e = pos;
+            pos += 1;
+            if matches!(bytes.get(pos), Some(b'+') | Some(b'-')) {
+                pos += 1;
+            }
+            let digits_start = pos;
+            while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+                pos += 1;
+            }
+            if pos == digits_start {
+                return Err(e - idx);
+            }
+        }
+
+        // A well-formed number is immediately followed by a delimiter, not
+        // more digits or another `.` (e.g. "1.2.3" or "01"); treat such
+        // trailing garbage as part of one malformed number token rather
+        // than silently splitting it into several valid ones.
+        if bytes.get(pos).is_some_and(|b| b.is_ascii_digit() || *b == b'.') {
+            while bytes
+                .get(pos)
+                .is_some_and(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'+' | b'-'))
+            {
+                pos += 1;
+            }
+            return Err(pos - idx);
+        }
+
+        Ok(pos - idx)
+    }
+
+    fn validate_number_token(&self, idx: usize) -> Result<Token<'a>, TokenizerError> {
+        match self.scan_number(idx) {
+            Ok(len) => {
+                let num = self.source[idx..idx + len]
+                    .parse::<f64>()
+                    .expect("scan_number only accepts syntax f64::parse understands");
+                Ok(Token::Number(num))
+            }
+            Err(len) => Err(TokenizerError::InvalidNumber(Span::new(idx, idx + len))),
         }
     }
 
-    fn verify_false_token(&self, idx: usize) -> Result<Token, TokenizerError> {
-        let false_slice = std::str::from_utf8(&self.input[idx..idx + 5])
-            .unwrap_or("Error validating null token. Invalid literal.");
+    fn verify_false_token(&self, idx: usize) -> Result<Token<'a>, TokenizerError> {
+        let false_slice = self.source.get(idx..idx + 5).unwrap_or("");
         match false_slice {
             "false" => Ok(Token::Bool(false)),
-            _ => Err(TokenizerError::InvalidLiteral),
+            _ => Err(TokenizerError::InvalidLiteral(Span::new(idx, idx + 5))),
         }
     }
 
-    fn verify_true_token(&self, idx: usize) -> Result<Token, TokenizerError> {
-        let true_slice = std::str::from_utf8(&self.input[idx..idx + 4])
-            .unwrap_or("Error validating null token. Invalid literal.");
+    fn verify_true_token(&self, idx: usize) -> Result<Token<'a>, TokenizerError> {
+        let true_slice = self.source.get(idx..idx + 4).unwrap_or("");
         match true_slice {
             "true" => Ok(Token::Bool(true)),
-            _ => Err(TokenizerError::InvalidLiteral),
+            _ => Err(TokenizerError::InvalidLiteral(Span::new(idx, idx + 4))),
         }
     }
 
-    fn verify_null_token(&self, idx: usize) -> Result<Token, TokenizerError> {
-        let null_slice = std::str::from_utf8(&self.input[idx..idx + 4])
-            .unwrap_or("Error validating null token. Invalid literal.");
+    fn verify_null_token(&self, idx: usize) -> Result<Token<'a>, TokenizerError> {
+        let null_slice = self.source.get(idx..idx + 4).unwrap_or("");
         match null_slice {
             "null" => Ok(Token::Null),
-            _ => Err(TokenizerError::InvalidLiteral),
+            _ => Err(TokenizerError::InvalidLiteral(Span::new(idx, idx + 4))),
         }
     }
 
-    pub fn tokenize(
-        self,
-    ) -> Result<Peekable<std::vec::IntoIter<Token>>, TokenizerError> {
-        let mut output = Vec::new();
-        let mut idx = 0;
-        while idx < self.input.len() {
-            let val = self.get_token(idx)?;
-            if val != Token::WhiteSpace {
-                output.push(val);
-                match output.last().unwrap() {
-                    Token::Bool(true) | Token::Null => idx += 4,
-                    Token::Bool(false) => idx += 5,
-                    Token::Number(val) => idx += val.to_string().len(),
-                    Token::String(val) => idx += val.len() + 2,
-                    _ => idx += 1,
+    /// Tokenizes the input, pairing each token with the byte span it was
+    /// read from so downstream consumers can report precise locations.
+    ///
+    /// A thin convenience wrapper around [`Tokenizer`]'s own `Iterator`
+    /// impl, for callers that want every token validated up front rather
+    /// than pulled lazily one at a time.
+    pub fn tokenize(self) -> Result<Vec<(Token<'a>, Span)>, TokenizerError> {
+        self.collect()
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<(Token<'a>, Span), TokenizerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes();
+        loop {
+            if self.idx >= bytes.len() {
+                return None;
+            }
+            let start = self.idx;
+            let val = match self.get_token(start) {
+                Ok(val) => val,
+                Err(err) => {
+                    // Stop iterating once a malformed token is hit; there's
+                    // no reliable way to resynchronize past it.
+                    self.idx = bytes.len();
+                    return Some(Err(err));
+                }
+            };
+            let next_idx = match &val {
+                Token::Bool(true) | Token::Null => start + 4,
+                Token::Bool(false) => start + 5,
+                Token::Number(_) => {
+                    start
+                        + self
+                            .scan_number(start)
+                            .expect("token was already validated as a number")
+                }
+                Token::String(_) => {
+                    start
+                        + self
+                            .scan_string(start)
+                            .expect("token was already validated as a string")
                 }
-            } else {
-                idx += 1
+                _ => start + 1,
+            };
+            self.idx = next_idx;
+            if val != Token::WhiteSpace {
+                return Some(Ok((val, Span::new(start, next_idx))));
             }
         }
-        Ok(output.into_iter().peekable())
     }
 }
 
@@ -151,6 +398,15 @@ impl Tokenizer {
 mod tests {
     use super::*;
 
+    fn tokens_only(lexer: Tokenizer) -> Vec<Token> {
+        lexer
+            .tokenize()
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect()
+    }
+
     #[test]
     fn all_syntax() {
         let lexer = Tokenizer::new("[]{}:,");
@@ -162,80 +418,172 @@ mod tests {
             Token::Colon,
             Token::Comma,
         ];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        assert_eq!(expected, tokens_only(lexer));
     }
 
     #[test]
     fn integer() {
         let lexer = Tokenizer::new("245");
         let expected = vec![Token::Number(245.0)];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        assert_eq!(expected, tokens_only(lexer));
     }
 
     #[test]
     fn float() {
         let lexer = Tokenizer::new("245.23");
         let expected = vec![Token::Number(245.23)];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        assert_eq!(expected, tokens_only(lexer));
     }
 
     #[test]
     fn negative() {
         let lexer = Tokenizer::new("-245");
         let expected = vec![Token::Number(-245.0)];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        assert_eq!(expected, tokens_only(lexer));
     }
 
     #[test]
     fn negative_float() {
         let lexer = Tokenizer::new("-245.23");
         let expected = vec![Token::Number(-245.23)];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        assert_eq!(expected, tokens_only(lexer));
+    }
+
+    #[test]
+    fn exponent() {
+        let lexer = Tokenizer::new("6.022e23");
+        let expected = vec![Token::Number(6.022e23)];
+        assert_eq!(expected, tokens_only(lexer));
+    }
+
+    #[test]
+    fn negative_exponent_with_sign() {
+        let lexer = Tokenizer::new("-2.5E-3");
+        let expected = vec![Token::Number(-2.5E-3)];
+        assert_eq!(expected, tokens_only(lexer));
+    }
+
+    #[test]
+    fn exponent_without_fraction() {
+        let lexer = Tokenizer::new("1e10");
+        let expected = vec![Token::Number(1e10)];
+        assert_eq!(expected, tokens_only(lexer));
+    }
+
+    #[test]
+    fn leading_zero_is_rejected() {
+        let lexer = Tokenizer::new("012");
+        assert!(matches!(lexer.tokenize(), Err(TokenizerError::InvalidNumber(_))));
+    }
+
+    #[test]
+    fn double_negative_is_rejected() {
+        let lexer = Tokenizer::new("--1");
+        assert!(matches!(lexer.tokenize(), Err(TokenizerError::InvalidNumber(_))));
+    }
+
+    #[test]
+    fn trailing_dot_without_digits_is_rejected() {
+        let lexer = Tokenizer::new("1.");
+        assert!(matches!(lexer.tokenize(), Err(TokenizerError::InvalidNumber(_))));
+    }
+
+    #[test]
+    fn extra_fraction_is_rejected() {
+        let lexer = Tokenizer::new("1.2.3");
+        assert!(matches!(lexer.tokenize(), Err(TokenizerError::InvalidNumber(_))));
     }
 
     #[test]
     fn string() {
         let lexer = Tokenizer::new("\"Abc-243.abc00\"");
-        let expected = vec![Token::String("Abc-243.abc00".to_string())];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        let expected = vec![Token::String(Cow::Borrowed("Abc-243.abc00"))];
+        assert_eq!(expected, tokens_only(lexer));
     }
 
     #[test]
     fn string_with_escaped_quote() {
         let lexer = Tokenizer::new("\"Abc-243.\\\"abc00\"");
-        let expected = vec![Token::String("Abc-243.\\\"abc00".to_string())];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        let expected = vec![Token::String(Cow::Owned("Abc-243.\"abc00".to_string()))];
+        assert_eq!(expected, tokens_only(lexer));
+    }
+
+    #[test]
+    fn string_borrows_from_source() {
+        let source = String::from("\"borrowed\"");
+        let lexer = Tokenizer::new(&source);
+        let tokens = lexer.tokenize().unwrap();
+        match &tokens[0].0 {
+            Token::String(s) => assert!(std::ptr::eq(s.as_ptr(), source.as_ptr().wrapping_add(1))),
+            _ => panic!("expected a string token"),
+        }
+    }
+
+    #[test]
+    fn string_with_common_escapes() {
+        let lexer = Tokenizer::new(r#""a\n\t\r\\\/\b\f""#);
+        let expected = vec![Token::String(Cow::Owned("a\n\t\r\\/\u{8}\u{C}".to_string()))];
+        assert_eq!(expected, tokens_only(lexer));
+    }
+
+    #[test]
+    fn string_with_unicode_escape() {
+        let lexer = Tokenizer::new("\"\\u00e9\"");
+        let expected = vec![Token::String(Cow::Owned("\u{e9}".to_string()))];
+        assert_eq!(expected, tokens_only(lexer));
+    }
+
+    #[test]
+    fn string_with_surrogate_pair() {
+        let lexer = Tokenizer::new("\"\\ud83d\\ude00\"");
+        let expected = vec![Token::String(Cow::Owned("\u{1f600}".to_string()))];
+        assert_eq!(expected, tokens_only(lexer));
+    }
+
+    #[test]
+    fn lone_high_surrogate_is_rejected() {
+        let lexer = Tokenizer::new(r#""\ud83d""#);
+        assert!(matches!(lexer.tokenize(), Err(TokenizerError::InvalidString(_))));
+    }
+
+    #[test]
+    fn lone_low_surrogate_is_rejected() {
+        let lexer = Tokenizer::new(r#""\ude00""#);
+        assert!(matches!(lexer.tokenize(), Err(TokenizerError::InvalidString(_))));
+    }
+
+    #[test]
+    fn unknown_escape_is_rejected() {
+        let lexer = Tokenizer::new(r#""\q""#);
+        assert!(matches!(lexer.tokenize(), Err(TokenizerError::InvalidString(_))));
+    }
+
+    #[test]
+    fn escaped_quote_at_string_start_is_handled() {
+        let lexer = Tokenizer::new(r#""\"abc""#);
+        let expected = vec![Token::String(Cow::Owned("\"abc".to_string()))];
+        assert_eq!(expected, tokens_only(lexer));
     }
 
     #[test]
     fn null() {
         let lexer = Tokenizer::new("null");
         let expected = vec![Token::Null];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        assert_eq!(expected, tokens_only(lexer));
     }
 
     #[test]
     fn bool_false() {
         let lexer = Tokenizer::new("false");
         let expected = vec![Token::Bool(false)];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        assert_eq!(expected, tokens_only(lexer));
     }
 
     #[test]
     fn bool_true() {
         let lexer = Tokenizer::new("true");
         let expected = vec![Token::Bool(true)];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        assert_eq!(expected, tokens_only(lexer));
     }
 
     #[test]
@@ -249,24 +597,40 @@ mod tests {
         let lexer = Tokenizer::new(sample_json);
         let expected = vec![
             Token::CurlyOpen,
-            Token::String("str".to_string()),
+            Token::String(Cow::Borrowed("str")),
             Token::Colon,
-            Token::String("value".to_string()),
+            Token::String(Cow::Borrowed("value")),
             Token::Comma,
-            Token::String("num".to_string()),
+            Token::String(Cow::Borrowed("num")),
             Token::Colon,
             Token::Number(123.0),
             Token::Comma,
-            Token::String("bool".to_string()),
+            Token::String(Cow::Borrowed("bool")),
             Token::Colon,
             Token::Bool(true),
             Token::Comma,
-            Token::String("null".to_string()),
+            Token::String(Cow::Borrowed("null")),
             Token::Colon,
             Token::Null,
             Token::CurlyClose,
         ];
-        let actual: Vec<Token> = lexer.tokenize().unwrap().collect();
-        assert_eq!(expected, actual);
+        assert_eq!(expected, tokens_only(lexer));
+    }
+
+    #[test]
+    fn spans_cover_each_token() {
+        let lexer = Tokenizer::new("{\"a\":1}");
+        let tokens = lexer.tokenize().unwrap();
+        let spans: Vec<Span> = tokens.into_iter().map(|(_, span)| span).collect();
+        assert_eq!(
+            spans,
+            vec![
+                Span::new(0, 1), // {
+                Span::new(1, 4), // "a"
+                Span::new(4, 5), // :
+                Span::new(5, 6), // 1
+                Span::new(6, 7), // }
+            ]
+        );
     }
 }
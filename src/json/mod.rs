@@ -0,0 +1,5 @@
+pub mod diagnostics;
+pub mod lexer;
+pub mod parser;
+pub mod serializer;
+pub mod stream;
@@ -1,90 +1,263 @@
-use super::lexer::Token;
+use super::diagnostics;
+use super::lexer::{LocatedError, Span, Token};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::iter::Peekable;
-use std::vec::IntoIter;
 
+/// A parsed JSON value, borrowing its strings directly from the source
+/// document the `Parser` was built from wherever the lexer didn't have to
+/// decode an escape.
 #[derive(Debug, PartialEq)]
-pub enum JsonValue {
-    Object(HashMap<String, JsonValue>),
-    Array(Vec<JsonValue>),
+pub enum JsonValue<'a> {
+    Object(HashMap<Cow<'a, str>, JsonValue<'a>>),
+    Array(Vec<JsonValue<'a>>),
+    String(Cow<'a, str>),
+    Number(f64),
+    Bool(bool),
+    Null,
+}
+
+/// An owned counterpart of [`JsonValue`], for callers that need the parsed
+/// document to outlive its source text.
+#[derive(Debug, PartialEq)]
+pub enum OwnedJsonValue {
+    Object(HashMap<String, OwnedJsonValue>),
+    Array(Vec<OwnedJsonValue>),
     String(String),
     Number(f64),
     Bool(bool),
     Null,
 }
 
-pub struct Parser {
-    tokens: Peekable<IntoIter<Token>>,
+impl<'a> JsonValue<'a> {
+    /// Clones every borrowed string to produce an owned value.
+    pub fn to_owned(&self) -> OwnedJsonValue {
+        match self {
+            JsonValue::Object(map) => OwnedJsonValue::Object(
+                map.iter()
+                    .map(|(key, value)| (key.to_string(), value.to_owned()))
+                    .collect(),
+            ),
+            JsonValue::Array(items) => {
+                OwnedJsonValue::Array(items.iter().map(JsonValue::to_owned).collect())
+            }
+            JsonValue::String(s) => OwnedJsonValue::String(s.to_string()),
+            JsonValue::Number(n) => OwnedJsonValue::Number(*n),
+            JsonValue::Bool(b) => OwnedJsonValue::Bool(*b),
+            JsonValue::Null => OwnedJsonValue::Null,
+        }
+    }
 }
 
-impl Parser {
-    pub fn new(tokens: Peekable<IntoIter<Token>>) -> Self {
-        Self { tokens }
+/// Parses a token stream into a [`JsonValue`].
+///
+/// Generic over the token source `I` (and its error type `E`) rather than
+/// the concrete `Tokenizer`, so the same parsing code drives both an
+/// in-memory `Tokenizer` and a streaming lexer read from `std::io::Read` –
+/// anything that yields tokens paired with the span they came from.
+pub struct Parser<'a, I, E>
+where
+    I: Iterator<Item = Result<(Token<'a>, Span), E>>,
+    E: LocatedError,
+{
+    source: &'a str,
+    tokens: Peekable<I>,
+    /// The span of the last token consumed, used to locate errors raised
+    /// once the token stream has already run dry (e.g. a truncated input).
+    last_span: Span,
+    /// How many objects/arrays deep the parser currently is.
+    depth: usize,
+    max_depth: usize,
+}
+
+/// The default limit on how deeply nested objects/arrays may be, chosen to
+/// comfortably fit the default stack size while still rejecting adversarial
+/// inputs like thousands of nested `[[[[...`.
+const DEFAULT_MAX_DEPTH: usize = 128;
+
+impl<'a, I, E> Parser<'a, I, E>
+where
+    I: Iterator<Item = Result<(Token<'a>, Span), E>>,
+    E: LocatedError,
+{
+    pub fn new(source: &'a str, tokens: I) -> Self {
+        Self::with_max_depth(source, tokens, DEFAULT_MAX_DEPTH)
     }
 
-    fn next_token(&mut self) -> Option<Token> {
-        self.tokens.next()
+    /// Like [`Parser::new`], but with a custom limit on object/array
+    /// nesting depth instead of the default of 128.
+    pub fn with_max_depth(source: &'a str, tokens: I, max_depth: usize) -> Self {
+        Self {
+            source,
+            tokens: tokens.peekable(),
+            last_span: Span::new(0, 0),
+            depth: 0,
+            max_depth,
+        }
+    }
+
+    /// Enters one more level of object/array nesting, or reports a clean
+    /// error at `span` (the opening `{`/`[`) if `max_depth` would be
+    /// exceeded, instead of recursing further and risking a stack overflow.
+    fn enter_nesting(&mut self, span: Span) -> Result<(), String> {
+        if self.depth >= self.max_depth {
+            return Err(self.error_at(
+                span,
+                &format!(
+                    "exceeded maximum nesting depth of {} at depth {}",
+                    self.max_depth, self.depth
+                ),
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn next_token(&mut self) -> Option<Result<(Token<'a>, Span), String>> {
+        match self.tokens.next() {
+            Some(Ok((token, span))) => {
+                self.last_span = span;
+                Some(Ok((token, span)))
+            }
+            Some(Err(err)) => Some(Err(self.error_at(err.location(), &err.to_string()))),
+            None => None,
+        }
+    }
+
+    fn peek_token(&mut self) -> Option<Result<&Token<'a>, String>> {
+        // Captured before `peek()` borrows `self.tokens`, so rendering the
+        // error afterward doesn't need a second borrow of `self`.
+        let source = self.source;
+        match self.tokens.peek() {
+            Some(Ok((token, _))) => Some(Ok(token)),
+            Some(Err(err)) => Some(Err(diagnostics::render(source, err.location(), &err.to_string()))),
+            None => None,
+        }
     }
 
-    fn peek_token(&mut self) -> Option<&Token> {
-        self.tokens.peek()
+    /// Renders `message` as a diagnostic pointing at `span` within the
+    /// original source, e.g. "error: expected colon at 3:14".
+    fn error_at(&self, span: Span, message: &str) -> String {
+        diagnostics::render(self.source, span, message)
     }
 }
 
-impl Parser {
-    pub fn parse(&mut self) -> Result<JsonValue, String> {
+impl<'a, I, E> Parser<'a, I, E>
+where
+    I: Iterator<Item = Result<(Token<'a>, Span), E>>,
+    E: LocatedError,
+{
+    /// Parses exactly one top-level JSON value and confirms nothing follows
+    /// it, so e.g. `"{}{}"` or `"1 2"` are rejected instead of silently
+    /// validating only their first value.
+    pub fn parse(&mut self) -> Result<JsonValue<'a>, String> {
+        let value = self.parse_value()?;
         match self.next_token() {
-            Some(Token::CurlyOpen) => self.parse_object(),
-            Some(Token::SquareOpen) => self.parse_array(),
-            Some(Token::String(s)) => Ok(JsonValue::String(s)),
-            Some(Token::Number(n)) => Ok(JsonValue::Number(n)),
-            Some(Token::Bool(b)) => Ok(JsonValue::Bool(b)),
-            Some(Token::Null) => Ok(JsonValue::Null),
-            _ => Err("Unexpected token".to_string()),
+            None => Ok(value),
+            Some(Ok((_, span))) => Err(self.error_at(span, "unexpected trailing data after document")),
+            Some(Err(err)) => Err(err),
         }
     }
 
-    fn parse_object(&mut self) -> Result<JsonValue, String> {
-        let mut map = std::collections::HashMap::new();
+    fn parse_value(&mut self) -> Result<JsonValue<'a>, String> {
+        match self.next_token() {
+            Some(Ok((Token::CurlyOpen, span))) => {
+                self.enter_nesting(span)?;
+                let result = self.parse_object();
+                self.depth -= 1;
+                result
+            }
+            Some(Ok((Token::SquareOpen, span))) => {
+                self.enter_nesting(span)?;
+                let result = self.parse_array();
+                self.depth -= 1;
+                result
+            }
+            Some(Ok((Token::String(s), _))) => Ok(JsonValue::String(s)),
+            Some(Ok((Token::Number(n), _))) => Ok(JsonValue::Number(n)),
+            Some(Ok((Token::Bool(b), _))) => Ok(JsonValue::Bool(b)),
+            Some(Ok((Token::Null, _))) => Ok(JsonValue::Null),
+            Some(Ok((_, span))) => Err(self.error_at(span, "unexpected token")),
+            Some(Err(err)) => Err(err),
+            None => Err(self.error_at(self.last_span, "unexpected end of input")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue<'a>, String> {
+        let mut map = HashMap::new();
         loop {
             match self.next_token() {
-                Some(Token::CurlyClose) => break,
-                Some(Token::String(key)) => {
-                    if let Some(Token::Colon) = self.next_token() {
-                        let value = self.parse()?;
-                        map.insert(key.clone(), value);
+                Some(Ok((Token::CurlyClose, _))) => break,
+                Some(Ok((Token::String(key), _))) => match self.next_token() {
+                    Some(Ok((Token::Colon, _))) => {
+                        let value = self.parse_value()?;
+                        map.insert(key, value);
                         match self.next_token() {
-                            Some(Token::Comma) => continue,
-                            Some(Token::CurlyClose) => break,
-                            _ => return Err("Expected comma or closing curly brace".to_string()),
+                            Some(Ok((Token::Comma, _))) => continue,
+                            Some(Ok((Token::CurlyClose, _))) => break,
+                            Some(Ok((_, span))) => {
+                                return Err(
+                                    self.error_at(span, "expected comma or closing curly brace")
+                                )
+                            }
+                            Some(Err(err)) => return Err(err),
+                            None => {
+                                return Err(self.error_at(
+                                    self.last_span,
+                                    "expected comma or closing curly brace",
+                                ))
+                            }
                         }
-                    } else {
-                        return Err("Expected colon".to_string());
                     }
+                    Some(Ok((_, span))) => return Err(self.error_at(span, "expected colon")),
+                    Some(Err(err)) => return Err(err),
+                    None => return Err(self.error_at(self.last_span, "expected colon")),
+                },
+                Some(Ok((_, span))) => {
+                    return Err(self.error_at(span, "expected string key or closing curly brace"))
+                }
+                Some(Err(err)) => return Err(err),
+                None => {
+                    return Err(self.error_at(
+                        self.last_span,
+                        "expected string key or closing curly brace",
+                    ))
                 }
-                _ => return Err("Expected string key or closing curly brace".to_string()),
             }
         }
         Ok(JsonValue::Object(map))
     }
 
-    fn parse_array(&mut self) -> Result<JsonValue, String> {
+    fn parse_array(&mut self) -> Result<JsonValue<'a>, String> {
         let mut vec = Vec::new();
         loop {
             match self.peek_token() {
-                Some(Token::SquareClose) => {
+                Some(Ok(Token::SquareClose)) => {
                     self.next_token();
                     break;
                 }
+                Some(Err(err)) => return Err(err),
                 _ => {
-                    let value = self.parse()?;
+                    let value = self.parse_value()?;
                     vec.push(value);
                     match self.peek_token() {
-                        Some(Token::Comma) => {
+                        Some(Ok(Token::Comma)) => {
                             self.next_token();
                         } // consume comma
-                        Some(Token::SquareClose) => continue,
-                        _ => return Err("Expected comma or closing square bracket".to_string()),
+                        Some(Ok(Token::SquareClose)) => continue,
+                        Some(Ok(_)) => {
+                            let (_, span) = self.next_token().unwrap()?;
+                            return Err(
+                                self.error_at(span, "expected comma or closing square bracket")
+                            );
+                        }
+                        Some(Err(err)) => return Err(err),
+                        None => {
+                            return Err(self.error_at(
+                                self.last_span,
+                                "expected comma or closing square bracket",
+                            ))
+                        }
                     }
                 }
             }
@@ -92,3 +265,65 @@ impl Parser {
         Ok(JsonValue::Array(vec))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::lexer::Tokenizer;
+
+    #[test]
+    fn to_owned_detaches_from_source() {
+        let source = String::from("{\"a\":[1,\"b\",null]}");
+        let value = Parser::new(&source, Tokenizer::new(&source)).parse().unwrap();
+        let owned = value.to_owned();
+        drop(source);
+
+        let mut expected = HashMap::new();
+        expected.insert(
+            "a".to_string(),
+            OwnedJsonValue::Array(vec![
+                OwnedJsonValue::Number(1.0),
+                OwnedJsonValue::String("b".to_string()),
+                OwnedJsonValue::Null,
+            ]),
+        );
+        assert_eq!(owned, OwnedJsonValue::Object(expected));
+    }
+
+    #[test]
+    fn deeply_nested_arrays_are_rejected_at_the_default_depth() {
+        let source = "[".repeat(200) + &"]".repeat(200);
+        let err = Parser::new(&source, Tokenizer::new(&source))
+            .parse()
+            .unwrap_err();
+        assert!(err.contains("exceeded maximum nesting depth"));
+    }
+
+    #[test]
+    fn with_max_depth_allows_nesting_within_the_custom_limit() {
+        let source = "[".repeat(5) + "1" + &"]".repeat(5);
+        let value = Parser::with_max_depth(&source, Tokenizer::new(&source), 10)
+            .parse()
+            .unwrap();
+        assert!(matches!(value, JsonValue::Array(_)));
+    }
+
+    #[test]
+    fn with_max_depth_rejects_nesting_beyond_the_custom_limit() {
+        let source = "[".repeat(5) + "1" + &"]".repeat(5);
+        let err = Parser::with_max_depth(&source, Tokenizer::new(&source), 3)
+            .parse()
+            .unwrap_err();
+        assert!(err.contains("exceeded maximum nesting depth"));
+    }
+
+    #[test]
+    fn trailing_data_after_the_top_level_value_is_rejected() {
+        for source in ["{}{}", "1 2", "truefalse"] {
+            let err = Parser::new(source, Tokenizer::new(source))
+                .parse()
+                .unwrap_err();
+            assert!(err.contains("trailing data"), "{source:?} -> {err}");
+        }
+    }
+}
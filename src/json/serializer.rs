@@ -0,0 +1,172 @@
+//! Turns a parsed [`JsonValue`] back into JSON text, either compact (via
+//! its `Display` impl, and therefore `to_string()`) or pretty-printed with
+//! [`JsonValue::to_string_pretty`]. Since `JsonValue::Object` is a
+//! `HashMap` with no iteration order of its own, both forms sort object
+//! keys first so output is stable across runs.
+
+use super::parser::JsonValue;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Write as _;
+
+fn sorted_keys<'a, 'b>(map: &'b HashMap<Cow<'a, str>, JsonValue<'a>>) -> Vec<&'b Cow<'a, str>> {
+    let mut keys: Vec<&Cow<'a, str>> = map.keys().collect();
+    keys.sort();
+    keys
+}
+
+/// Writes `s` as a JSON string literal: wrapped in quotes, with `"` and
+/// `\` escaped and control characters rendered as `\uXXXX`.
+fn write_escaped<W: fmt::Write>(w: &mut W, s: &str) -> fmt::Result {
+    w.write_char('"')?;
+    for ch in s.chars() {
+        match ch {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            c if (c as u32) < 0x20 => write!(w, "\\u{:04x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
+
+impl<'a> fmt::Display for JsonValue<'a> {
+    /// Compact rendering: no whitespace between tokens.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonValue::Object(map) => {
+                f.write_char('{')?;
+                for (i, key) in sorted_keys(map).into_iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_escaped(f, key)?;
+                    write!(f, ":{}", map[key])?;
+                }
+                f.write_char('}')
+            }
+            JsonValue::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            }
+            JsonValue::String(s) => write_escaped(f, s),
+            // NaN/Infinity have no JSON representation; emit `null` rather
+            // than `f64`'s `inf`/`-inf`/`NaN`, which isn't valid JSON.
+            JsonValue::Number(n) if !n.is_finite() => f.write_str("null"),
+            JsonValue::Number(n) => write!(f, "{n}"),
+            JsonValue::Bool(b) => write!(f, "{b}"),
+            JsonValue::Null => f.write_str("null"),
+        }
+    }
+}
+
+impl<'a> JsonValue<'a> {
+    /// Renders this value as pretty-printed JSON: each object/array element
+    /// on its own line, indented by `indent` spaces per level of nesting,
+    /// with a space after each `:`.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, indent, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, indent: usize, depth: usize) {
+        match self {
+            JsonValue::Object(map) if map.is_empty() => out.push_str("{}"),
+            JsonValue::Object(map) => {
+                out.push_str("{\n");
+                let keys = sorted_keys(map);
+                let pad = " ".repeat(indent * (depth + 1));
+                let last = keys.len() - 1;
+                for (i, key) in keys.into_iter().enumerate() {
+                    out.push_str(&pad);
+                    write_escaped(out, key).expect("writing to a String never fails");
+                    out.push_str(": ");
+                    map[key].write_pretty(out, indent, depth + 1);
+                    if i < last {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                out.push_str(&" ".repeat(indent * depth));
+                out.push('}');
+            }
+            JsonValue::Array(items) if items.is_empty() => out.push_str("[]"),
+            JsonValue::Array(items) => {
+                out.push_str("[\n");
+                let pad = " ".repeat(indent * (depth + 1));
+                let last = items.len() - 1;
+                for (i, item) in items.iter().enumerate() {
+                    out.push_str(&pad);
+                    item.write_pretty(out, indent, depth + 1);
+                    if i < last {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                out.push_str(&" ".repeat(indent * depth));
+                out.push(']');
+            }
+            _ => out.push_str(&self.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::lexer::Tokenizer;
+    use crate::json::parser::Parser;
+
+    fn parse(source: &str) -> JsonValue<'_> {
+        Parser::new(source, Tokenizer::new(source)).parse().unwrap()
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let value = parse(r#"{"b":1,"a":[true,null,"x"]}"#);
+        assert_eq!(value.to_string(), r#"{"a":[true,null,"x"],"b":1}"#);
+    }
+
+    #[test]
+    fn compact_escapes_control_characters() {
+        let value = parse(r#""a\nb""#);
+        assert_eq!(value.to_string(), "\"a\\u000ab\"");
+    }
+
+    #[test]
+    fn compact_numbers_have_no_trailing_zero() {
+        let value = parse("123.0");
+        assert_eq!(value.to_string(), "123");
+    }
+
+    #[test]
+    fn pretty_indents_nested_structures() {
+        let value = parse(r#"{"a":[1,2]}"#);
+        assert_eq!(value.to_string_pretty(2), "{\n  \"a\": [\n    1,\n    2\n  ]\n}");
+    }
+
+    #[test]
+    fn pretty_renders_empty_containers_without_newlines() {
+        let value = parse(r#"{"a":[],"b":{}}"#);
+        assert_eq!(
+            value.to_string_pretty(2),
+            "{\n  \"a\": [],\n  \"b\": {}\n}"
+        );
+    }
+
+    #[test]
+    fn non_finite_numbers_serialize_as_null() {
+        // `1e999` overflows f64::parse to infinity but lexes as a valid number.
+        let value = parse("1e999");
+        assert_eq!(value.to_string(), "null");
+        assert_eq!(value.to_string_pretty(2), "null");
+    }
+}
@@ -0,0 +1,463 @@
+//! A lexer that reads from any `std::io::Read` instead of requiring the
+//! whole document up front, so validating a large file doesn't mean
+//! holding all of it in memory. It reads in fixed-size chunks, growing an
+//! internal buffer only until it holds the token currently being assembled,
+//! then drains the completed bytes from the front, so memory stays bounded
+//! by the largest single token plus one chunk rather than the whole document. [`Parser`](super::parser::Parser)
+//! is generic over its token source, so this feeds the same parsing code
+//! as the in-memory [`Tokenizer`](super::lexer::Tokenizer).
+
+use super::lexer::{LocatedError, Span, Token, TokenizerError};
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, Read};
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// An error from [`StreamingTokenizer`]: either a malformed token (the same
+/// ones the in-memory lexer reports) or a failure reading from the
+/// underlying `Read`.
+#[derive(Debug)]
+pub enum StreamError {
+    Tokenizer(TokenizerError),
+    Io(io::Error),
+}
+
+impl From<TokenizerError> for StreamError {
+    fn from(err: TokenizerError) -> Self {
+        Self::Tokenizer(err)
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Tokenizer(err) => write!(f, "{err}"),
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl LocatedError for StreamError {
+    fn location(&self) -> Span {
+        match self {
+            Self::Tokenizer(err) => err.span(),
+            // A read failure isn't tied to a particular byte of the document.
+            Self::Io(_) => Span::new(0, 0),
+        }
+    }
+}
+
+/// A lexer over any `R: Read`, yielding one token at a time.
+pub struct StreamingTokenizer<R> {
+    reader: R,
+    buf: Vec<u8>,
+    /// Absolute offset of `buf[0]` in the full stream, so spans reported
+    /// from a partially-drained buffer still point at the right place.
+    buf_start: usize,
+    eof: bool,
+}
+
+impl<R: Read> StreamingTokenizer<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            buf_start: 0,
+            eof: false,
+        }
+    }
+
+    /// Reads from the underlying reader until `buf` holds at least
+    /// `at_least` bytes, or the reader is exhausted.
+    fn fill_to(&mut self, at_least: usize) -> io::Result<()> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        while self.buf.len() < at_least && !self.eof {
+            let n = self.reader.read(&mut chunk)?;
+            if n == 0 {
+                self.eof = true;
+            } else {
+                self.buf.extend_from_slice(&chunk[..n]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops `n` fully-tokenized bytes from the front of `buf`.
+    fn drop_front(&mut self, n: usize) {
+        self.buf.drain(..n);
+        self.buf_start += n;
+    }
+
+    fn emit(&mut self, token: Token<'static>, len: usize) -> Result<(Token<'static>, Span), StreamError> {
+        let start = self.buf_start;
+        self.drop_front(len);
+        Ok((token, Span::new(start, start + len)))
+    }
+
+    fn read_exact_literal(&mut self, literal: &str) -> Result<(), StreamError> {
+        self.fill_to(literal.len())?;
+        if self.buf.get(..literal.len()) == Some(literal.as_bytes()) {
+            Ok(())
+        } else {
+            Err(TokenizerError::InvalidLiteral(Span::new(
+                self.buf_start,
+                self.buf_start + literal.len().min(self.buf.len()),
+            ))
+            .into())
+        }
+    }
+
+    /// Scans a number starting at the front of `buf`, growing it until a
+    /// byte outside the number grammar is seen or the reader is exhausted.
+    ///
+    /// The run is collected byte by byte since `buf` can't be un-consumed,
+    /// but unlike `f64::parse` alone, `is_valid_number_grammar` then checks
+    /// the full RFC 8259 grammar over the collected run before it's trusted,
+    /// so this agrees with `Tokenizer::scan_number` on what counts as a
+    /// number instead of silently accepting whatever `f64::parse` does.
+    fn read_number(&mut self) -> Result<(f64, usize), StreamError> {
+        let mut pos = 0;
+        loop {
+            self.fill_to(pos + 1)?;
+            match self.buf.get(pos) {
+                Some(b) if b.is_ascii_digit() || matches!(b, b'-' | b'+' | b'.' | b'e' | b'E') => {
+                    pos += 1;
+                }
+                _ => break,
+            }
+        }
+        if !Self::is_valid_number_grammar(&self.buf[..pos]) {
+            return Err(
+                TokenizerError::InvalidNumber(Span::new(self.buf_start, self.buf_start + pos)).into(),
+            );
+        }
+        match std::str::from_utf8(&self.buf[..pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            Some(n) => Ok((n, pos)),
+            None => Err(
+                TokenizerError::InvalidNumber(Span::new(self.buf_start, self.buf_start + pos)).into(),
+            ),
+        }
+    }
+
+    /// Checks `bytes` against the same number grammar `Tokenizer::scan_number`
+    /// enforces: an optional `-`, then a lone `0` or a `[1-9][0-9]*` integer
+    /// part, an optional `.` followed by at least one digit, and an optional
+    /// `e`/`E` (with optional sign) followed by at least one digit – with no
+    /// bytes left over. This is what rules out `+5`, `01`, `1.`, and `1.e5`,
+    /// all of which `f64::parse` accepts but RFC 8259 doesn't.
+    fn is_valid_number_grammar(bytes: &[u8]) -> bool {
+        let mut pos = 0;
+        if bytes.first() == Some(&b'-') {
+            pos += 1;
+        }
+        match bytes.get(pos) {
+            Some(b'0') => pos += 1,
+            Some(b'1'..=b'9') => {
+                pos += 1;
+                while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+                    pos += 1;
+                }
+            }
+            _ => return false,
+        }
+
+        if bytes.get(pos) == Some(&b'.') {
+            pos += 1;
+            let digits_start = pos;
+            while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+                pos += 1;
+            }
+            if pos == digits_start {
+                return false;
+            }
+        }
+
+        if matches!(bytes.get(pos), Some(b'e') | Some(b'E')) {
+            pos += 1;
+            if matches!(bytes.get(pos), Some(b'+') | Some(b'-')) {
+                pos += 1;
+            }
+            let digits_start = pos;
+            while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+                pos += 1;
+            }
+            if pos == digits_start {
+                return false;
+            }
+        }
+
+        pos == bytes.len()
+    }
+
+    /// Scans a string starting at the opening `"` at the front of `buf`,
+    /// growing it until an unescaped closing `"` is found, then decodes
+    /// escapes the same way `Tokenizer::validate_string_token` does.
+    fn read_string(&mut self) -> Result<(String, usize), StreamError> {
+        let mut pos = 1;
+        loop {
+            self.fill_to(pos + 1)?;
+            match self.buf.get(pos) {
+                None => {
+                    return Err(TokenizerError::InvalidString(Span::new(
+                        self.buf_start,
+                        self.buf_start + self.buf.len(),
+                    ))
+                    .into())
+                }
+                Some(b'"') => break,
+                Some(b'\\') => pos += 2,
+                Some(_) => pos += 1,
+            }
+        }
+        let decoded = decode_string(&self.buf[1..pos], self.buf_start)?;
+        Ok((decoded, pos + 1))
+    }
+}
+
+/// Decodes JSON string escapes in `content` (the bytes between the
+/// quotes), the same set `Tokenizer::validate_string_token` supports.
+fn decode_string(content: &[u8], span_start: usize) -> Result<String, StreamError> {
+    let invalid = |at: usize| TokenizerError::InvalidString(Span::new(span_start, span_start + at));
+    let mut out = String::new();
+    let mut i = 0;
+    while i < content.len() {
+        if content[i] != b'\\' {
+            let start = i;
+            while i < content.len() && content[i] != b'\\' {
+                i += 1;
+            }
+            out.push_str(std::str::from_utf8(&content[start..i]).map_err(|_| invalid(i))?);
+            continue;
+        }
+        i += 1;
+        match content.get(i) {
+            Some(b'"') => {
+                out.push('"');
+                i += 1;
+            }
+            Some(b'\\') => {
+                out.push('\\');
+                i += 1;
+            }
+            Some(b'/') => {
+                out.push('/');
+                i += 1;
+            }
+            Some(b'b') => {
+                out.push('\u{0008}');
+                i += 1;
+            }
+            Some(b'f') => {
+                out.push('\u{000C}');
+                i += 1;
+            }
+            Some(b'n') => {
+                out.push('\n');
+                i += 1;
+            }
+            Some(b'r') => {
+                out.push('\r');
+                i += 1;
+            }
+            Some(b't') => {
+                out.push('\t');
+                i += 1;
+            }
+            Some(b'u') => {
+                i += 1;
+                let unit = read_hex4(content, i).ok_or_else(|| invalid(i))?;
+                i += 4;
+                let scalar = if (0xD800..=0xDBFF).contains(&unit) {
+                    let low = (content.get(i) == Some(&b'\\') && content.get(i + 1) == Some(&b'u'))
+                        .then(|| read_hex4(content, i + 2))
+                        .flatten()
+                        .filter(|low| (0xDC00..=0xDFFF).contains(low))
+                        .ok_or_else(|| invalid(i))?;
+                    i += 6;
+                    0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00)
+                } else if (0xDC00..=0xDFFF).contains(&unit) {
+                    return Err(invalid(i).into());
+                } else {
+                    unit
+                };
+                out.push(char::from_u32(scalar).ok_or_else(|| invalid(i))?);
+            }
+            _ => return Err(invalid(i).into()),
+        }
+    }
+    Ok(out)
+}
+
+/// Reads the four hex digits at `pos` as a UTF-16 code unit.
+fn read_hex4(bytes: &[u8], pos: usize) -> Option<u32> {
+    let digits = std::str::from_utf8(bytes.get(pos..pos + 4)?).ok()?;
+    u32::from_str_radix(digits, 16).ok()
+}
+
+impl<R: Read> Iterator for StreamingTokenizer<R> {
+    type Item = Result<(Token<'static>, Span), StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Err(err) = self.fill_to(1) {
+                return Some(Err(err.into()));
+            }
+            match self.buf.first().copied() {
+                None => return None,
+                Some(b'{') => return Some(self.emit(Token::CurlyOpen, 1)),
+                Some(b'}') => return Some(self.emit(Token::CurlyClose, 1)),
+                Some(b'[') => return Some(self.emit(Token::SquareOpen, 1)),
+                Some(b']') => return Some(self.emit(Token::SquareClose, 1)),
+                Some(b',') => return Some(self.emit(Token::Comma, 1)),
+                Some(b':') => return Some(self.emit(Token::Colon, 1)),
+                Some(b'"') => {
+                    return Some(self.read_string().map(|(s, len)| {
+                        let start = self.buf_start;
+                        self.drop_front(len);
+                        (Token::String(Cow::Owned(s)), Span::new(start, start + len))
+                    }))
+                }
+                Some(b'n') => {
+                    return Some(self.read_exact_literal("null").map(|()| {
+                        let start = self.buf_start;
+                        self.drop_front(4);
+                        (Token::Null, Span::new(start, start + 4))
+                    }))
+                }
+                Some(b't') => {
+                    return Some(self.read_exact_literal("true").map(|()| {
+                        let start = self.buf_start;
+                        self.drop_front(4);
+                        (Token::Bool(true), Span::new(start, start + 4))
+                    }))
+                }
+                Some(b'f') => {
+                    return Some(self.read_exact_literal("false").map(|()| {
+                        let start = self.buf_start;
+                        self.drop_front(5);
+                        (Token::Bool(false), Span::new(start, start + 5))
+                    }))
+                }
+                Some(b'0'..=b'9') | Some(b'-') | Some(b'+') => {
+                    return Some(self.read_number().map(|(n, len)| {
+                        let start = self.buf_start;
+                        self.drop_front(len);
+                        (Token::Number(n), Span::new(start, start + len))
+                    }))
+                }
+                Some(_) => self.drop_front(1), // whitespace or other insignificant byte
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::parser::Parser;
+
+    fn tokens(input: &str) -> Vec<Token<'static>> {
+        StreamingTokenizer::new(input.as_bytes())
+            .map(|result| result.unwrap().0)
+            .collect()
+    }
+
+    #[test]
+    fn tokenizes_basic_syntax() {
+        assert_eq!(
+            tokens("[true,false,null]"),
+            vec![
+                Token::SquareOpen,
+                Token::Bool(true),
+                Token::Comma,
+                Token::Bool(false),
+                Token::Comma,
+                Token::Null,
+                Token::SquareClose,
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenizes_numbers_and_strings_across_chunk_boundaries() {
+        // Sized so the string spans the first chunk boundary and `123.5`
+        // straddles the second one (bytes 16381..16386 around 16384).
+        let big_string = "a".repeat(CHUNK_SIZE * 2 - 8);
+        let input = format!("[\"{big_string}\", 123.5]");
+        assert_eq!(
+            tokens(&input),
+            vec![
+                Token::SquareOpen,
+                Token::String(Cow::Owned(big_string)),
+                Token::Comma,
+                Token::Number(123.5),
+                Token::SquareClose,
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_escapes_like_the_in_memory_lexer() {
+        assert_eq!(
+            tokens(r#""a\nb""#),
+            vec![Token::String(Cow::Owned("a\nb".to_string()))]
+        );
+    }
+
+    #[test]
+    fn rejects_unterminated_string() {
+        let mut stream = StreamingTokenizer::new("\"abc".as_bytes());
+        assert!(matches!(
+            stream.next(),
+            Some(Err(StreamError::Tokenizer(TokenizerError::InvalidString(_))))
+        ));
+    }
+
+    #[test]
+    fn rejects_leading_zero_and_leading_plus_like_the_in_memory_lexer() {
+        for input in ["01", "+5"] {
+            let mut stream = StreamingTokenizer::new(input.as_bytes());
+            assert!(
+                matches!(
+                    stream.next(),
+                    Some(Err(StreamError::Tokenizer(TokenizerError::InvalidNumber(_))))
+                ),
+                "{input:?} should have been rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_trailing_dot_and_dot_then_exponent_like_the_in_memory_lexer() {
+        for input in ["1.", "1.e5"] {
+            let mut stream = StreamingTokenizer::new(input.as_bytes());
+            assert!(
+                matches!(
+                    stream.next(),
+                    Some(Err(StreamError::Tokenizer(TokenizerError::InvalidNumber(_))))
+                ),
+                "{input:?} should have been rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn parser_accepts_a_streaming_token_source() {
+        let value = Parser::new("", StreamingTokenizer::new(r#"{"a":[1,2]}"#.as_bytes()))
+            .parse()
+            .unwrap();
+        assert_eq!(value.to_string(), r#"{"a":[1,2]}"#);
+    }
+}
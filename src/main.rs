@@ -2,11 +2,6 @@ use std::env;
 use std::fs;
 use parse_json::json::{lexer::Tokenizer, parser::Parser};
 
-#[derive(Debug)]
-struct Name {
-    field: word
-}
-
 fn main() {
     // Get the command line arguments.
     let args: Vec<String> = env::args().collect();
@@ -28,12 +23,10 @@ fn main() {
     };
 
 
-    let tokens = Tokenizer::new(&file_contents).tokenize();
-    let parser = Parser::new(tokens.unwrap()).parse();
-
+    let parser = Parser::new(&file_contents, Tokenizer::new(&file_contents)).parse();
 
     match parser {
-        Ok(_) => println!("This is valid JSON. Great!"),
+        Ok(value) => println!("{}", value.to_string_pretty(2)),
         Err(err) => println!("{}", err)
     }
 }